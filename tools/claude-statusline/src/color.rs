@@ -0,0 +1,114 @@
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
+
+use crate::config::Colors;
+use crate::shell::ShellType;
+
+/// A themeable color: either a basic SGR code (for the 16-color palette and
+/// attributes like bold/reset) or a 24-bit truecolor RGB triple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Basic(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Parse a `#rrggbb` hex string or one of the named palette entries.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if let Some(hex) = s.strip_prefix('#') {
+            if hex.len() != 6 || !hex.is_ascii() {
+                return Err(format!("invalid hex color: {s}"));
+            }
+            let byte = |i: usize| {
+                u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| format!("invalid hex color: {s}"))
+            };
+            return Ok(Color::Rgb(byte(0)?, byte(2)?, byte(4)?));
+        }
+
+        match s {
+            "red" => Ok(Color::Basic(31)),
+            "green" => Ok(Color::Basic(32)),
+            "yellow" => Ok(Color::Basic(33)),
+            "blue" => Ok(Color::Basic(34)),
+            "magenta" => Ok(Color::Basic(35)),
+            "cyan" => Ok(Color::Basic(36)),
+            "dim" | "bright_black" => Ok(Color::Basic(90)),
+            "bold" => Ok(Color::Basic(1)),
+            "reset" | "none" => Ok(Color::Basic(0)),
+            other => Err(format!("unknown color name: {other}")),
+        }
+    }
+
+    /// The SGR escape sequence for this color, or an empty string when
+    /// coloring is disabled (`NO_COLOR` set).
+    pub fn sgr(self, enabled: bool) -> String {
+        if !enabled {
+            return String::new();
+        }
+        match self {
+            Color::Basic(code) => format!("\x1b[{code}m"),
+            Color::Rgb(r, g, b) => format!("\x1b[38;2;{r};{g};{b}m"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Color::parse(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Colors resolved to literal escape strings (or empty strings, if coloring
+/// is disabled) once at startup, so the rest of the program just interpolates
+/// them like the old constants.
+pub struct ResolvedColors {
+    pub red: String,
+    pub green: String,
+    pub yellow: String,
+    pub dim: String,
+    pub bold: String,
+    pub reset: String,
+    pub enabled: bool,
+    pub shell: ShellType,
+}
+
+impl ResolvedColors {
+    /// Resolve a `Color` that wasn't part of the base palette (e.g. a
+    /// per-field sidecar color rule) through the same enabled/shell rules.
+    pub fn themed(&self, color: Color) -> String {
+        self.shell.wrap(&color.sgr(self.enabled))
+    }
+}
+
+impl Colors {
+    pub fn resolve(&self, enabled: bool, shell: ShellType) -> ResolvedColors {
+        let themed = |c: Color| shell.wrap(&c.sgr(enabled));
+        ResolvedColors {
+            red: themed(self.red),
+            green: themed(self.green),
+            yellow: themed(self.yellow),
+            dim: themed(self.dim),
+            bold: themed(self.bold),
+            reset: themed(self.reset),
+            enabled,
+            shell,
+        }
+    }
+}
+
+/// Whether escape sequences should be emitted at all.
+///
+/// Deliberate deviation from a non-tty-also-strips rule: the statusline's
+/// stdout is always a pipe back to its caller (Claude Code), never a tty, so
+/// a `std::io::IsTerminal` check here would make every segment permanently
+/// monochrome for the only consumer that exists. `NO_COLOR` is therefore the
+/// sole opt-out; shell-prompt embedding (where escapes must not corrupt
+/// column counting, not be removed) goes through `--shell`/`ShellType`
+/// instead of this flag.
+pub fn is_enabled() -> bool {
+    std::env::var_os("NO_COLOR").is_none()
+}
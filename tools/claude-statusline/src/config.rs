@@ -0,0 +1,100 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use crate::color::Color;
+use crate::segments::Segment;
+use crate::shell::ShellType;
+use crate::sidecar::SidecarConfig;
+
+/// User-facing config for `~/.config/claude-statusline/config.ron`.
+///
+/// Every field has a `Default`, so a partial file only needs to specify the
+/// bits the user wants to change; missing fields fall back to the built-in
+/// defaults below.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub segments: Vec<Segment>,
+    pub separator: String,
+    pub colors: Colors,
+    pub thresholds: Thresholds,
+    pub sidecars: Vec<SidecarConfig>,
+    /// The shell to target for zero-width escape wrapping. Overridden by
+    /// `--shell` on the command line.
+    pub shell: ShellType,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct Colors {
+    pub red: Color,
+    pub green: Color,
+    pub yellow: Color,
+    pub dim: Color,
+    pub bold: Color,
+    pub reset: Color,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct Thresholds {
+    pub ctx_warn_pct: f64,
+    pub ctx_crit_pct: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            segments: vec![
+                Segment::Model,
+                Segment::Context,
+                Segment::Cost,
+                Segment::Timer,
+                Segment::Sidecar("vibes".to_string()),
+                Segment::Cwd,
+                Segment::Git,
+            ],
+            separator: " | ".to_string(),
+            colors: Colors::default(),
+            thresholds: Thresholds::default(),
+            sidecars: vec![SidecarConfig::default()],
+            shell: ShellType::default(),
+        }
+    }
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Colors {
+            red: Color::Basic(31),
+            green: Color::Basic(32),
+            yellow: Color::Basic(33),
+            dim: Color::Basic(90),
+            bold: Color::Basic(1),
+            reset: Color::Basic(0),
+        }
+    }
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            ctx_warn_pct: 50.0,
+            ctx_crit_pct: 80.0,
+        }
+    }
+}
+
+fn config_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".config/claude-statusline/config.ron")
+}
+
+/// Load the user config, falling back to defaults if the file is absent or
+/// fails to parse.
+pub fn load() -> Config {
+    match std::fs::read_to_string(config_path()) {
+        Ok(contents) => ron::from_str(&contents).unwrap_or_default(),
+        Err(_) => Config::default(),
+    }
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::color::ResolvedColors;
+
+/// Render the git segment for `cwd`, or `None` if it isn't inside a repo.
+pub fn render(cwd: &str, colors: &ResolvedColors) -> Option<String> {
+    let root = find_repo_root(Path::new(cwd))?;
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&root)
+        .args(["status", "--porcelain=v2", "--branch"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    Some(format_status(&parse(&text), colors))
+}
+
+fn find_repo_root(start: &Path) -> Option<PathBuf> {
+    let mut dir = start;
+    loop {
+        if dir.join(".git").exists() {
+            return Some(dir.to_path_buf());
+        }
+        dir = dir.parent()?;
+    }
+}
+
+struct Status {
+    branch: String,
+    staged: usize,
+    modified: usize,
+    untracked: usize,
+    ahead: usize,
+    behind: usize,
+}
+
+fn parse(text: &str) -> Status {
+    let mut head: Option<String> = None;
+    let mut oid: Option<String> = None;
+    let mut staged = 0;
+    let mut modified = 0;
+    let mut untracked = 0;
+    let mut ahead = 0;
+    let mut behind = 0;
+
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix("# branch.head ") {
+            head = Some(rest.to_string());
+        } else if let Some(rest) = line.strip_prefix("# branch.oid ") {
+            oid = Some(rest.chars().take(7).collect());
+        } else if let Some(rest) = line.strip_prefix("# branch.ab ") {
+            for field in rest.split_whitespace() {
+                if let Some(n) = field.strip_prefix('+') {
+                    ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = field.strip_prefix('-') {
+                    behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") || line.starts_with("u ") {
+            if let Some(xy) = line.split(' ').nth(1) {
+                let mut chars = xy.chars();
+                let x = chars.next().unwrap_or('.');
+                let y = chars.next().unwrap_or('.');
+                if x != '.' {
+                    staged += 1;
+                }
+                if y != '.' {
+                    modified += 1;
+                }
+            }
+        } else if line.starts_with("? ") {
+            untracked += 1;
+        }
+    }
+
+    let branch = match head.as_deref() {
+        Some("(detached)") | None => oid.unwrap_or_else(|| "?".to_string()),
+        Some(name) => name.to_string(),
+    };
+
+    Status {
+        branch,
+        staged,
+        modified,
+        untracked,
+        ahead,
+        behind,
+    }
+}
+
+fn format_status(status: &Status, colors: &ResolvedColors) -> String {
+    let dirty = status.staged > 0 || status.modified > 0 || status.untracked > 0;
+    let branch_color = if dirty { &colors.yellow } else { &colors.green };
+    let reset = &colors.reset;
+
+    let mut out = format!("{branch_color}{}{reset}", status.branch);
+    if status.staged > 0 {
+        out.push_str(&format!(" {}✚{}{reset}", colors.green, status.staged));
+    }
+    if status.modified > 0 {
+        out.push_str(&format!(" {}●{}{reset}", colors.yellow, status.modified));
+    }
+    if status.untracked > 0 {
+        out.push_str(&format!(" {}…{}{reset}", colors.dim, status.untracked));
+    }
+    if status.ahead > 0 {
+        out.push_str(&format!(" {}↑{}{reset}", colors.green, status.ahead));
+    }
+    if status.behind > 0 {
+        out.push_str(&format!(" {}↓{}{reset}", colors.red, status.behind));
+    }
+    out
+}
@@ -1,15 +1,17 @@
+mod color;
+mod config;
+mod git;
+mod segments;
+mod shell;
+mod sidecar;
+
 use serde::Deserialize;
-use std::fs;
 use std::io::{self, Read};
 use std::path::Path;
 
-// ANSI codes
-const RED: &str = "\x1b[31m";
-const GREEN: &str = "\x1b[32m";
-const YELLOW: &str = "\x1b[33m";
-const DIM: &str = "\x1b[90m";
-const BOLD: &str = "\x1b[1m";
-const RESET: &str = "\x1b[0m";
+use color::ResolvedColors;
+use config::Config;
+use segments::Segment;
 
 #[derive(Deserialize, Default)]
 struct StatusData {
@@ -36,13 +38,6 @@ struct Cost {
     total_duration_ms: Option<u64>,
 }
 
-#[derive(Deserialize, Default)]
-struct VibesState {
-    mood: Option<String>,
-    injected: Option<bool>,
-    vibe: Option<String>,
-}
-
 fn format_duration(ms: u64) -> String {
     let total_minutes = ms / 60_000;
     let hours = total_minutes / 60;
@@ -55,122 +50,128 @@ fn format_duration(ms: u64) -> String {
     }
 }
 
-fn get_vibes(session_id: &str) -> String {
-    let vibes_path = format!("/tmp/claude-vibes/{}.json", session_id);
-    let path = Path::new(&vibes_path);
-
-    if !path.exists() {
-        return format!("{DIM}--{RESET}");
-    }
-
-    let content = match fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(_) => return format!("{DIM}--{RESET}"),
-    };
-
-    let vs: VibesState = match serde_json::from_str(&content) {
-        Ok(v) => v,
-        Err(_) => return format!("{DIM}--{RESET}"),
-    };
-
-    let mood = vs.mood.as_deref().unwrap_or("neutral");
-    let mood_colored = match mood {
-        "frustrated" => format!("{RED}frustrated{RESET}"),
-        "excited" => format!("{GREEN}excited{RESET}"),
-        "confused" => format!("{YELLOW}confused{RESET}"),
-        "neutral" => format!("{DIM}neutral{RESET}"),
-        other => format!("{DIM}{other}{RESET}"),
-    };
-
-    if vs.injected.unwrap_or(false) {
-        if let Some(vibe) = &vs.vibe {
-            let truncated = if vibe.chars().count() > 45 {
-                let s: String = vibe.chars().take(45).collect();
-                format!("{s}...")
+fn render_segment(
+    segment: &Segment,
+    data: &StatusData,
+    cfg: &Config,
+    colors: &ResolvedColors,
+) -> String {
+    let bold = &colors.bold;
+    let dim = &colors.dim;
+    let reset = &colors.reset;
+
+    match segment {
+        Segment::Model => {
+            let model_name = data
+                .model
+                .as_ref()
+                .and_then(|m| m.display_name.as_deref())
+                .unwrap_or("?");
+            format!("{bold}{model_name}{reset}")
+        }
+        Segment::Context => {
+            let used_pct = data
+                .context_window
+                .as_ref()
+                .and_then(|c| c.used_percentage)
+                .unwrap_or(0.0);
+            let ctx_color = if used_pct < cfg.thresholds.ctx_warn_pct {
+                &colors.green
+            } else if used_pct < cfg.thresholds.ctx_crit_pct {
+                &colors.yellow
             } else {
-                vibe.clone()
+                &colors.red
             };
-            return format!("{mood_colored} {DIM}\"{truncated}\"{RESET}");
+            format!("{ctx_color}ctx {used_pct:.0}%{reset}")
+        }
+        Segment::Cost => {
+            let cost = data
+                .cost
+                .as_ref()
+                .and_then(|c| c.total_cost_usd)
+                .unwrap_or(0.0);
+            format!("{dim}${cost:.2}{reset}")
+        }
+        Segment::Timer => data
+            .cost
+            .as_ref()
+            .and_then(|c| c.total_duration_ms)
+            .map(|ms| format!("{dim}{}{reset}", format_duration(ms)))
+            .unwrap_or_else(|| format!("{dim}--{reset}")),
+        Segment::Sidecar(name) => {
+            let session_id = data.session_id.as_deref().unwrap_or("unknown");
+            let cwd = data.cwd.as_deref().unwrap_or("");
+            match sidecar::find(&cfg.sidecars, name) {
+                Some(sc) => sidecar::render(sc, session_id, cwd, colors),
+                None => format!("{dim}--{reset}"),
+            }
+        }
+        Segment::Cwd => {
+            let cwd = data.cwd.as_deref().unwrap_or("");
+            let cwd_basename = Path::new(cwd)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("?");
+            format!("{bold}{cwd_basename}{reset}")
+        }
+        Segment::Git => {
+            let cwd = data.cwd.as_deref().unwrap_or("");
+            git::render(cwd, colors).unwrap_or_else(|| format!("{dim}--{reset}"))
         }
     }
+}
 
-    mood_colored
+/// Parse `--shell <bash|zsh|fish|none>` off argv.
+///
+/// `Ok(None)` means the flag wasn't passed at all, so the caller should fall
+/// back to `cfg.shell`. A flag that *was* passed but names an unknown shell
+/// is `Err` rather than silently falling back: wrapping escapes for the
+/// wrong shell (or not wrapping them at all) corrupts prompt width, so a
+/// typo should fail loudly instead of emitting raw escapes.
+fn shell_flag() -> Result<Option<shell::ShellType>, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--shell" {
+            let value = args.next().ok_or("--shell requires a value")?;
+            return shell::ShellType::parse(&value)
+                .map(Some)
+                .ok_or_else(|| format!("unknown --shell value: {value}"));
+        }
+    }
+    Ok(None)
 }
 
 fn main() {
+    let cfg = config::load();
+    let shell = match shell_flag() {
+        Ok(shell) => shell.unwrap_or(cfg.shell),
+        Err(e) => {
+            eprintln!("statusline: {e}");
+            std::process::exit(1);
+        }
+    };
+    let colors = cfg.colors.resolve(color::is_enabled(), shell);
+
     let mut input = String::new();
     if io::stdin().read_to_string(&mut input).is_err() || input.trim().is_empty() {
-        println!("{DIM}statusline: no data{RESET}");
+        println!("{}statusline: no data{}", colors.dim, colors.reset);
         return;
     }
 
     let data: StatusData = match serde_json::from_str(&input) {
         Ok(d) => d,
         Err(_) => {
-            println!("{DIM}statusline: invalid json{RESET}");
+            println!("{}statusline: invalid json{}", colors.dim, colors.reset);
             return;
         }
     };
 
-    // 1. Model name (bold)
-    let model_name = data
-        .model
-        .as_ref()
-        .and_then(|m| m.display_name.as_deref())
-        .unwrap_or("?");
-    let model_part = format!("{BOLD}{model_name}{RESET}");
-
-    // 2. Context usage % (colored)
-    let used_pct = data
-        .context_window
-        .as_ref()
-        .and_then(|c| c.used_percentage)
-        .unwrap_or(0.0);
-    let ctx_color = if used_pct < 50.0 {
-        GREEN
-    } else if used_pct < 80.0 {
-        YELLOW
-    } else {
-        RED
-    };
-    let ctx_part = format!("{ctx_color}ctx {used_pct:.0}%{RESET}");
-
-    // 3. Cost (dim)
-    let cost = data
-        .cost
-        .as_ref()
-        .and_then(|c| c.total_cost_usd)
-        .unwrap_or(0.0);
-    let cost_part = format!("{DIM}${cost:.2}{RESET}");
-
-    // 4. Session timer (dim) — from total_duration_ms
-    let timer_part = data
-        .cost
-        .as_ref()
-        .and_then(|c| c.total_duration_ms)
-        .map(|ms| format!("{DIM}{}{RESET}", format_duration(ms)))
-        .unwrap_or_else(|| format!("{DIM}--{RESET}"));
-
-    // 5. Vibes mood
-    let session_id = data.session_id.as_deref().unwrap_or("unknown");
-    let vibes_part = get_vibes(session_id);
-
-    // 6. CWD basename (bold)
-    let cwd = data.cwd.as_deref().unwrap_or("");
-    let cwd_basename = Path::new(cwd)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("?");
-    let cwd_part = format!("{BOLD}{cwd_basename}{RESET}");
-
-    let sep = format!(" {DIM}|{RESET} ");
-    let parts = [
-        model_part,
-        ctx_part,
-        cost_part,
-        timer_part,
-        vibes_part,
-        cwd_part,
-    ];
+    let parts: Vec<String> = cfg
+        .segments
+        .iter()
+        .map(|seg| render_segment(seg, &data, &cfg, &colors))
+        .collect();
+
+    let sep = format!("{}{}{}", colors.dim, cfg.separator, colors.reset);
     println!("{}", parts.join(&sep));
 }
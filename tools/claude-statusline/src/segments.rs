@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+/// One piece of the rendered statusline, in the order the config lists them.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Segment {
+    Model,
+    Context,
+    Cost,
+    Timer,
+    Cwd,
+    Git,
+    /// A sidecar declared in `Config::sidecars`, referenced by name.
+    Sidecar(String),
+}
@@ -0,0 +1,39 @@
+use serde::Deserialize;
+
+/// The shell the rendered statusline is embedded in, so escape sequences can
+/// be wrapped for correct prompt column-counting.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellType {
+    Bash,
+    Zsh,
+    Fish,
+    #[default]
+    None,
+}
+
+impl ShellType {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "bash" => Some(ShellType::Bash),
+            "zsh" => Some(ShellType::Zsh),
+            "fish" => Some(ShellType::Fish),
+            "none" => Some(ShellType::None),
+            _ => None,
+        }
+    }
+
+    /// Wrap a non-empty escape sequence in this shell's zero-width
+    /// delimiters. Only escape sequences should be passed here — visible
+    /// text must never be wrapped.
+    pub fn wrap(self, escape: &str) -> String {
+        if escape.is_empty() {
+            return String::new();
+        }
+        match self {
+            ShellType::Bash => format!("\\[{escape}\\]"),
+            ShellType::Zsh => format!("%{{{escape}%}}"),
+            ShellType::Fish | ShellType::None => escape.to_string(),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::color::{Color, ResolvedColors};
+
+/// A single external JSON "sidecar" segment, declared in the config.
+///
+/// `path` is a template over `{session_id}`/`{cwd}`; `template` is a template
+/// over the JSON object's own fields, e.g. `{mood} "{vibe}"`. A field missing
+/// from the JSON object falls back to `defaults`, if one is configured for
+/// it; otherwise rendering stops there, leaving everything after it out
+/// rather than printing a placeholder.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SidecarConfig {
+    pub name: String,
+    pub path: String,
+    pub template: String,
+    pub truncate_width: usize,
+    pub colors: Vec<ColorRule>,
+    /// If set, everything in `template` past the first field is only
+    /// rendered when this JSON field is present and `true`.
+    pub show_rest_if: Option<String>,
+    /// Fallback values for fields absent from the sidecar's JSON.
+    pub defaults: Vec<FieldDefault>,
+}
+
+/// Colors a field of a sidecar when its value matches.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ColorRule {
+    pub field: String,
+    pub value: String,
+    pub color: Color,
+}
+
+/// The value substituted for `field` when it's absent from the sidecar's
+/// JSON, so e.g. a missing `mood` can still read as "neutral" instead of
+/// cutting the whole segment short.
+#[derive(Debug, Deserialize, Clone)]
+pub struct FieldDefault {
+    pub field: String,
+    pub value: String,
+}
+
+impl Default for SidecarConfig {
+    fn default() -> Self {
+        SidecarConfig {
+            name: "vibes".to_string(),
+            path: "/tmp/claude-vibes/{session_id}.json".to_string(),
+            template: "{mood} \"{vibe}\"".to_string(),
+            truncate_width: 45,
+            colors: vec![
+                ColorRule {
+                    field: "mood".to_string(),
+                    value: "frustrated".to_string(),
+                    color: Color::Basic(31),
+                },
+                ColorRule {
+                    field: "mood".to_string(),
+                    value: "excited".to_string(),
+                    color: Color::Basic(32),
+                },
+                ColorRule {
+                    field: "mood".to_string(),
+                    value: "confused".to_string(),
+                    color: Color::Basic(33),
+                },
+            ],
+            show_rest_if: Some("injected".to_string()),
+            defaults: vec![FieldDefault {
+                field: "mood".to_string(),
+                value: "neutral".to_string(),
+            }],
+        }
+    }
+}
+
+/// Look up a declared sidecar by name.
+pub fn find<'a>(sidecars: &'a [SidecarConfig], name: &str) -> Option<&'a SidecarConfig> {
+    sidecars.iter().find(|s| s.name == name)
+}
+
+/// Load and render a sidecar segment, falling back to a dim `--` on any
+/// missing file or parse error.
+pub fn render(cfg: &SidecarConfig, session_id: &str, cwd: &str, colors: &ResolvedColors) -> String {
+    let path = cfg.path.replace("{session_id}", session_id).replace("{cwd}", cwd);
+    let dim = &colors.dim;
+    let reset = &colors.reset;
+
+    let value = match std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+    {
+        Some(v) => v,
+        None => return format!("{dim}--{reset}"),
+    };
+
+    let Some(obj) = value.as_object() else {
+        return format!("{dim}--{reset}");
+    };
+
+    let rendered = render_template(cfg, obj, colors);
+    if rendered.is_empty() {
+        format!("{dim}--{reset}")
+    } else {
+        rendered
+    }
+}
+
+/// Interpolate `cfg.template` against `obj`. A field missing from `obj`
+/// falls back to `cfg.defaults`; if it has none, rendering stops there
+/// (without a trailing placeholder or literal text). Rendering also stops
+/// past the first field if `show_rest_if` names a field that isn't `true`.
+fn render_template(cfg: &SidecarConfig, obj: &Map<String, Value>, colors: &ResolvedColors) -> String {
+    let reset = &colors.reset;
+    let mut out = String::new();
+    let mut rest = cfg.template.as_str();
+    let mut field_index = 0;
+
+    loop {
+        let Some(start) = rest.find('{') else {
+            out.push_str(rest);
+            return out;
+        };
+
+        let literal = &rest[..start];
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let field = &after[..end];
+
+        let raw: &str = match obj.get(field).and_then(Value::as_str) {
+            Some(v) => v,
+            None => match default_for(cfg, field) {
+                Some(v) => v,
+                None => return out,
+            },
+        };
+
+        let gated_off = field_index > 0
+            && cfg.show_rest_if.as_ref().is_some_and(|gate| {
+                !obj.get(gate).and_then(Value::as_bool).unwrap_or(false)
+            });
+        if gated_off {
+            return out;
+        }
+
+        out.push_str(literal);
+        let truncated = truncate(raw, cfg.truncate_width);
+        let color = color_for(cfg, field, raw, colors);
+        out.push_str(&color);
+        out.push_str(&truncated);
+        out.push_str(reset);
+
+        rest = &after[end + 1..];
+        field_index += 1;
+    }
+}
+
+fn default_for<'a>(cfg: &'a SidecarConfig, field: &str) -> Option<&'a str> {
+    cfg.defaults
+        .iter()
+        .find(|d| d.field == field)
+        .map(|d| d.value.as_str())
+}
+
+fn color_for(cfg: &SidecarConfig, field: &str, value: &str, colors: &ResolvedColors) -> String {
+    cfg.colors
+        .iter()
+        .find(|rule| rule.field == field && rule.value == value)
+        .map(|rule| colors.themed(rule.color))
+        .unwrap_or_else(|| colors.dim.clone())
+}
+
+fn truncate(s: &str, width: usize) -> String {
+    if s.chars().count() > width {
+        let truncated: String = s.chars().take(width).collect();
+        format!("{truncated}...")
+    } else {
+        s.to_string()
+    }
+}